@@ -14,12 +14,40 @@
 //! that relies on `typenum`: the UUIDs can be made to use your
 //! crate's re-export of `typenum`, in case your users have an
 //! incompatible version.
+//!
+//! The one exception is [`uuid_bytes!`](macro@crate::uuid_bytes),
+//! which produces a `::typenum::TArr` of byte constants rather than a
+//! single `Unsigned`.
+//!
+//! Malformed input to any of these macros is reported as a regular
+//! compile error pointing at the offending token, rather than a
+//! panic from within the proc-macro.
+//!
+//! [`identify`]'s `Id` trait lives in the separate
+//! [`typenum_uuid_core`](https://docs.rs/typenum_uuid_core) crate,
+//! since a `proc-macro = true` crate like this one can't export
+//! anything but macros.
 
 use uuid::Uuid;
 use std::iter;
 
 extern crate proc_macro;
-use proc_macro::*;
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream as TokenStream2, TokenTree};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    LitFloat, LitInt, LitStr, Path, Token,
+};
+
+/// Custom keywords used to recognize the `urn:uuid:` prefix that
+/// `Uuid::parse_str` also accepts, and the `v5 = "..."` mode of
+/// `#[identify]`.
+mod kw {
+    syn::custom_keyword!(urn);
+    syn::custom_keyword!(uuid);
+    syn::custom_keyword!(v5);
+}
 
 /// Appends an identifier to a type path
 ///
@@ -27,14 +55,12 @@ use proc_macro::*;
 /// ```ignore
 /// $prefix :: $id
 /// ```
-fn prefixed_ident(prefix: &TokenStream, id: &str)->impl Iterator<Item=TokenTree> {
-    prefix.clone().into_iter().chain(
-        vec![
-            Punct::new(':', Spacing::Joint).into(),
-            Punct::new(':', Spacing::Alone).into(),
-            Ident::new(id, Span::call_site()).into()
-        ].into_iter()
-    )
+fn prefixed_ident(prefix: &TokenStream2, id: &str)->impl Iterator<Item=TokenTree> {
+    prefix.clone().into_iter().chain([
+        Punct::new(':', Spacing::Joint).into(),
+        Punct::new(':', Spacing::Alone).into(),
+        Ident::new(id, Span::call_site()).into()
+    ])
 }
 
 /// A mirror of how `typenum` describes unsigned integers:
@@ -47,7 +73,7 @@ enum TypenumUint {
 
 impl From<u128> for TypenumUint {
     fn from(x:u128)->Self {
-        if x == 0 { return Self::Term; }
+        if x == 0 { Self::Term }
         else { Self::Lsb( Box::new(Self::from(x >> 1)), (x & 1) != 0 ) }
     }
 }
@@ -56,7 +82,7 @@ impl TypenumUint {
     /// Write `self` into `ts`.
     ///
     /// `prefix` is the location of the `typenum` crate.
-    fn write_ts(&self, prefix: &TokenStream, ts: &mut TokenStream) {
+    fn write_ts(&self, prefix: &TokenStream2, ts: &mut TokenStream2) {
         match self {
             Self::Term => ts.extend(prefixed_ident(prefix, "UTerm")),
             Self::Lsb(high, bit) => {
@@ -75,6 +101,20 @@ impl TypenumUint {
             }
         }
     }
+
+    /// Whether `self` matches `typenum`'s normalized `Unsigned`
+    /// representation: zero is `UTerm` alone, and every nonzero
+    /// value's innermost (most significant) bit is `1`, i.e. there
+    /// are no redundant leading `UInt<.., B0>` layers.
+    fn is_canonical(&self) -> bool {
+        match self {
+            Self::Term => true,
+            Self::Lsb(high, bit) => match high.as_ref() {
+                Self::Term => *bit,
+                _ => high.is_canonical(),
+            },
+        }
+    }
 }
 
 /// Convert a Uuid object into a TokenStream
@@ -85,37 +125,266 @@ impl TypenumUint {
 ///
 /// `prefix` should be the path to the `typenum` crate at the macro
 /// expansion point.
-fn uuid_to_tokenstream(uuid: Uuid, prefix: TokenStream)->TokenStream {
-    let mut result = TokenStream::new();
-    TypenumUint::from(uuid.as_u128()).write_ts(&prefix, &mut result);
+fn uuid_to_tokenstream(uuid: Uuid, prefix: TokenStream2) -> TokenStream2 {
+    let value = TypenumUint::from(uuid.as_u128());
+    debug_assert!(
+        value.is_canonical(),
+        "TypenumUint::from should always produce typenum's normalized Unsigned representation"
+    );
+    let mut result = TokenStream2::new();
+    value.write_ts(&prefix, &mut result);
     result
 }
 
-/// Separate local from global macro arguments.
-///
-/// The macros in this crate all allow `| path::to::typenum` to be
-/// appended to the regular arguments in order to specify where to
-/// find `typenum`.  This function is responsible for finding and
-/// interpreting this, and using the default value of `::typenum`
-/// if none is given.
-fn split_off_prefix(args: TokenStream) -> (TokenStream, TokenStream) {
-    let mut args = args.into_iter();
-    let local = (&mut args).take_while(
-        |tt| match tt {
-            TokenTree::Punct(ref p) if p.as_char() == '|' => false,
-            _ => true
-        }
-    ).collect();
-    let mut prefix:TokenStream = args.collect();
-    if prefix.is_empty() {
-        let x:Vec<TokenTree> = vec![
-            Punct::new(':', Spacing::Joint).into(),
-            Punct::new(':', Spacing::Alone).into(),
-            Ident::new("typenum", Span::call_site()).into()
-        ];
-        prefix = x.into_iter().collect();
+/// Convert a Uuid's bytes into a `typenum::TArr` TokenStream
+///
+/// The resulting stream contains a type-level array of the UUID's
+/// 16 bytes in their canonical (big-endian) order, each a
+/// `typenum::consts::U0`..`U255` constant, for code that wants
+/// byte-addressable access instead of a single `Unsigned`.
+///
+/// `prefix` should be the path to the `typenum` crate at the macro
+/// expansion point.
+fn uuid_bytes_to_tokenstream(uuid: Uuid, prefix: TokenStream2) -> TokenStream2 {
+    let mut result = TokenStream2::new();
+    result.extend(prefixed_ident(&prefix, "ATerm"));
+    for &byte in uuid.as_bytes().iter().rev() {
+        let mut wrapped = TokenStream2::new();
+        wrapped.extend(prefixed_ident(&prefix, "TArr"));
+        wrapped.extend(iter::once::<TokenTree>(
+            Punct::new('<', Spacing::Alone).into()
+        ));
+        wrapped.extend(prefixed_ident(&prefix, &format!("U{}", byte)));
+        wrapped.extend(iter::once::<TokenTree>(
+            Punct::new(',', Spacing::Alone).into()
+        ));
+        wrapped.extend(result);
+        wrapped.extend(iter::once::<TokenTree>(
+            Punct::new('>', Spacing::Alone).into()
+        ));
+        result = wrapped;
+    }
+    result
+}
+
+/// The default location of the `typenum` crate, used when a macro
+/// invocation doesn't supply its own `| path::to::typenum`.
+fn default_prefix() -> TokenStream2 {
+    quote::quote!(::typenum)
+}
+
+/// The optional `| path::to::typenum` suffix shared by every macro
+/// in this crate.
+///
+/// This is what `split_off_prefix` used to do by hand, splitting the
+/// incoming token stream on the first top-level `|`. As a `Parse`
+/// impl it gets proper error spans for free, and composes with
+/// whatever comes before it in a larger `Parse` impl.
+struct TypenumPrefix(Option<Path>);
+
+impl Parse for TypenumPrefix {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            Ok(TypenumPrefix(Some(input.call(Path::parse_mod_style)?)))
+        } else {
+            Ok(TypenumPrefix(None))
+        }
+    }
+}
+
+impl TypenumPrefix {
+    fn into_tokens(self) -> TokenStream2 {
+        match self.0 {
+            Some(path) => quote::quote!(#path),
+            None => default_prefix(),
+        }
+    }
+}
+
+/// A UUID spelled out as bare tokens: hex digits and dashes, with an
+/// optional leading `urn:uuid:`, in whatever grouping
+/// `Uuid::parse_str` is willing to accept (simple, hyphenated,
+/// braced, or urn form).
+///
+/// Hex groups that start with a letter lex as `Ident`s and those
+/// that start with a digit lex as `LitInt`s or, if they contain an
+/// `e`/`.` in a position that looks like an exponent or decimal
+/// point, `LitFloat`s; a bare string literal is accepted too, for
+/// callers who would rather quote the whole thing.
+struct UuidLit {
+    text: String,
+    span: Span,
+}
+
+/// Consume a run of bare hex-digit tokens (`Ident`s, `LitInt`s,
+/// `LitFloat`s, string literals, `{...}` groups, and `-` separators) until `stop`
+/// says to halt, concatenating them into the textual form
+/// `Uuid::parse_str` expects. Shared by `UuidLit` and `Namespace`,
+/// which only differ in what they stop on (`|` vs. `,`).
+fn parse_hex_fragments(
+    input: ParseStream,
+    stop: impl Fn(ParseStream) -> bool,
+) -> syn::Result<(String, Option<Span>)> {
+    let mut text = String::new();
+    let mut span: Option<Span> = None;
+    macro_rules! extend_span {
+        ($s:expr) => {
+            span = Some(match span {
+                Some(prev) => prev.join($s).unwrap_or(prev),
+                None => $s,
+            })
+        };
+    }
+
+    while !input.is_empty() && !stop(input) {
+        if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            extend_span!(lit.span());
+            text.push_str(&lit.value());
+        } else if input.peek(LitInt) {
+            let lit: LitInt = input.parse()?;
+            extend_span!(lit.span());
+            text.push_str(&lit.to_string());
+        } else if input.peek(LitFloat) {
+            // A digit-led hex group followed by an `e`/`.` can lex as
+            // a float literal rather than an int -- e.g. the `e5`
+            // segment of `1e5f38d0` makes the whole group `1e5f38d0`
+            // ineligible for `LitInt`, same as plain `1e5` would be.
+            let lit: LitFloat = input.parse()?;
+            extend_span!(lit.span());
+            text.push_str(&lit.to_string());
+        } else if input.peek(syn::Ident) {
+            let id: syn::Ident = input.parse()?;
+            extend_span!(id.span());
+            text.push_str(&id.to_string());
+        } else if input.peek(Token![-]) {
+            let dash: Token![-] = input.parse()?;
+            extend_span!(dash.span());
+            text.push('-');
+        } else if input.peek(syn::token::Brace) {
+            // The braced form, `{a65ff38d-...}`, arrives as one group.
+            fn never(_: ParseStream) -> bool { false }
+            let content;
+            syn::braced!(content in input);
+            extend_span!(content.span());
+            let (inner, _) = parse_hex_fragments(&content, never)?;
+            text.push('{');
+            text.push_str(&inner);
+            text.push('}');
+        } else if let Ok(lit) = input.fork().parse::<syn::Lit>() {
+            // Catch-all for any other literal shape Rust's lexer
+            // might produce in this position; stringify it verbatim
+            // rather than rejecting it outright.
+            let _ = lit;
+            let lit: syn::Lit = input.parse()?;
+            extend_span!(lit.span());
+            text.push_str(&quote::ToTokens::to_token_stream(&lit).to_string());
+        } else {
+            return Err(input.error(
+                "expected 32 hex digits, optionally hyphenated or `urn:uuid:`-prefixed, found this token"
+            ));
+        }
+    }
+
+    Ok((text, span))
+}
+
+impl Parse for UuidLit {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut text = String::new();
+        let mut prefix_span: Option<Span> = None;
+
+        if input.peek(kw::urn) {
+            let urn: kw::urn = input.parse()?;
+            input.parse::<Token![:]>()?;
+            input.parse::<kw::uuid>()?;
+            input.parse::<Token![:]>()?;
+            prefix_span = Some(urn.span());
+            text.push_str("urn:uuid:");
+        }
+
+        if input.is_empty() || input.peek(Token![|]) {
+            return Err(input.error("expected a UUID, found nothing"));
+        }
+
+        let (rest, rest_span) = parse_hex_fragments(input, |input| input.peek(Token![|]))?;
+        text.push_str(&rest);
+
+        let span = match (prefix_span, rest_span) {
+            (Some(p), Some(r)) => p.join(r).unwrap_or(p),
+            (Some(p), None) => p,
+            (None, Some(r)) => r,
+            (None, None) => Span::call_site(),
+        };
+        Ok(UuidLit { text, span })
+    }
+}
+
+/// A namespace UUID, as accepted by `uuid_v5!`/`uuid_v3!`: either a
+/// literal UUID or one of `Uuid`'s well-known `NAMESPACE_*`
+/// constants.
+enum Namespace {
+    Literal(UuidLit),
+    WellKnown(Uuid),
+}
+
+impl Namespace {
+    fn well_known(name: &str) -> Option<Uuid> {
+        match name {
+            "NAMESPACE_DNS" => Some(Uuid::NAMESPACE_DNS),
+            "NAMESPACE_URL" => Some(Uuid::NAMESPACE_URL),
+            "NAMESPACE_OID" => Some(Uuid::NAMESPACE_OID),
+            "NAMESPACE_X500" => Some(Uuid::NAMESPACE_X500),
+            _ => None,
+        }
+    }
+
+    fn uuid(&self) -> syn::Result<Uuid> {
+        match self {
+            Namespace::WellKnown(uuid) => Ok(*uuid),
+            Namespace::Literal(lit) => Uuid::parse_str(&lit.text).map_err(|e| {
+                syn::Error::new(lit.span, format!("invalid namespace UUID: {}", e))
+            }),
+        }
+    }
+}
+
+impl Parse for Namespace {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Ident) {
+            let fork = input.fork();
+            let id: syn::Ident = fork.parse()?;
+            if let Some(uuid) = Self::well_known(&id.to_string()) {
+                input.parse::<syn::Ident>()?;
+                return Ok(Namespace::WellKnown(uuid));
+            }
+        }
+
+        let (text, span) = parse_hex_fragments(input, |input| input.peek(Token![,]))?;
+        if text.is_empty() {
+            return Err(input.error(
+                "expected a namespace UUID, or one of NAMESPACE_DNS/NAMESPACE_URL/NAMESPACE_OID/NAMESPACE_X500"
+            ));
+        }
+        Ok(Namespace::Literal(UuidLit { text, span: span.unwrap_or_else(Span::call_site) }))
+    }
+}
+
+/// The full argument list of the `uuid!` macro: a literal followed
+/// by the usual optional `| path::to::typenum`.
+struct UuidMacroInput {
+    lit: UuidLit,
+    prefix: TypenumPrefix,
+}
+
+impl Parse for UuidMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(UuidMacroInput {
+            lit: input.parse()?,
+            prefix: input.parse()?,
+        })
     }
-    (local, prefix)
 }
 
 /// Construct a new random UUID
@@ -149,25 +418,379 @@ fn split_off_prefix(args: TokenStream) -> (TokenStream, TokenStream) {
 /// // must_be_different(T1, T1);  // Compile Error
 /// ```
 #[proc_macro]
-pub fn uuid_new_v4(args: TokenStream)->TokenStream {
-    let (args, prefix) = split_off_prefix(args);
-    assert!(args.is_empty(), "v4 UUIDs take no arguments");
-    uuid_to_tokenstream(Uuid::new_v4(), prefix)
+pub fn uuid_new_v4(args: TokenStream) -> TokenStream {
+    let prefix = match syn::parse::<TypenumPrefix>(args) {
+        Ok(prefix) => prefix,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    uuid_to_tokenstream(Uuid::new_v4(), prefix.into_tokens()).into()
 }
 
 /// Construct a typenum UUID
 ///
-/// This macro parses its argument as a UUID 
+/// This macro parses its argument as a UUID
 /// and returns it as a `typenum::Unsigned` type:
 ///
 /// ```edition2018
 /// # use typenum_uuid::uuid;
 /// type Id = uuid!(a65ff38d-b5b2-48d0-b03a-bdf468523d2e);
 /// ```
+///
+/// A malformed UUID is reported as a `compile_error!` pointing at
+/// the offending tokens, rather than a panic.
+#[proc_macro]
+pub fn uuid(args: TokenStream) -> TokenStream {
+    let input = match syn::parse::<UuidMacroInput>(args) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let uuid = match Uuid::parse_str(&input.lit.text) {
+        Ok(uuid) => uuid,
+        Err(e) => {
+            return syn::Error::new(input.lit.span, format!("invalid UUID: {}", e))
+                .to_compile_error()
+                .into();
+        }
+    };
+    uuid_to_tokenstream(uuid, input.prefix.into_tokens()).into()
+}
+
+/// The argument list shared by `uuid_v5!` and `uuid_v3!`:
+/// `namespace, "name" | path::to::typenum`.
+struct NamespaceMacroInput {
+    namespace: Namespace,
+    name: LitStr,
+    prefix: TypenumPrefix,
+}
+
+impl Parse for NamespaceMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let namespace = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let name = input.parse()?;
+        let prefix = input.parse()?;
+        Ok(NamespaceMacroInput { namespace, name, prefix })
+    }
+}
+
+/// Shared implementation of `uuid_v5!`/`uuid_v3!`: parse a namespace
+/// and a name, then hash them with `hash` (either `Uuid::new_v5` or
+/// `Uuid::new_v3`) to get a deterministic UUID.
+fn uuid_from_namespace(args: TokenStream, hash: impl Fn(&Uuid, &[u8]) -> Uuid) -> TokenStream {
+    let input = match syn::parse::<NamespaceMacroInput>(args) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let namespace = match input.namespace.uuid() {
+        Ok(uuid) => uuid,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let uuid = hash(&namespace, input.name.value().as_bytes());
+    uuid_to_tokenstream(uuid, input.prefix.into_tokens()).into()
+}
+
+/// Construct a deterministic, name-based (v5, SHA-1) UUID
+///
+/// Unlike `uuid_new_v4!`, the result only depends on its arguments,
+/// so the same source always produces the same type-level ID across
+/// builds. `namespace` is either a literal UUID or one of
+/// `NAMESPACE_DNS`/`NAMESPACE_URL`/`NAMESPACE_OID`/`NAMESPACE_X500`:
+///
+/// ```
+/// use typenum_uuid::uuid_v5;
+/// type ID = uuid_v5!(NAMESPACE_DNS, "example.com");
+/// ```
 #[proc_macro]
-pub fn uuid(args: TokenStream)->TokenStream {
-    let (args, prefix) = split_off_prefix(args);
-    let args:String = args.to_string()
-        .chars().filter(|c| !c.is_whitespace()).collect();
-    uuid_to_tokenstream(Uuid::parse_str(&*args).unwrap(), prefix)
+pub fn uuid_v5(args: TokenStream) -> TokenStream {
+    uuid_from_namespace(args, Uuid::new_v5)
+}
+
+/// Construct a deterministic, name-based (v3, MD5) UUID
+///
+/// The MD5-based counterpart to [`uuid_v5!`](macro@crate::uuid_v5);
+/// see its documentation for the argument syntax. Prefer `uuid_v5!`
+/// unless you need v3 specifically for compatibility with other
+/// systems.
+#[proc_macro]
+pub fn uuid_v3(args: TokenStream) -> TokenStream {
+    uuid_from_namespace(args, Uuid::new_v3)
+}
+
+/// The argument list of `uuid_v7!`: a 48-bit millisecond timestamp,
+/// an optional seed for the random bits, and the usual optional
+/// `| path::to::typenum`.
+struct V7MacroInput {
+    millis: u64,
+    seed: Option<u128>,
+    prefix: TypenumPrefix,
+}
+
+impl Parse for V7MacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let millis_lit: LitInt = input.parse()?;
+        let millis: u64 = millis_lit.base10_parse()?;
+        if millis >= (1u64 << 48) {
+            return Err(syn::Error::new(
+                millis_lit.span(),
+                "v7 timestamp must fit in 48 bits (milliseconds since the Unix epoch)",
+            ));
+        }
+
+        let seed = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let seed_lit: LitInt = input.parse()?;
+            Some(seed_lit.base10_parse()?)
+        } else {
+            None
+        };
+
+        Ok(V7MacroInput { millis, seed, prefix: input.parse()? })
+    }
+}
+
+/// A fixed, arbitrary fill for the random bits of a v7 UUID when no
+/// seed is given, so that two calls with the same timestamp and no
+/// seed still produce the same UUID.
+const DEFAULT_V7_PAD: [u8; 10] = [0x42; 10];
+
+/// Fill the 10 random bytes of a v7 UUID from an optional seed, so
+/// the same seed always produces the same bytes.
+fn v7_rand_bytes(seed: Option<u128>) -> [u8; 10] {
+    match seed {
+        None => DEFAULT_V7_PAD,
+        Some(seed) => {
+            let be = seed.to_be_bytes();
+            let mut rand_bytes = [0u8; 10];
+            rand_bytes.copy_from_slice(&be[6..16]);
+            rand_bytes
+        }
+    }
+}
+
+/// Construct a time-ordered (v7) UUID with an explicit timestamp
+///
+/// Proc macros shouldn't depend on wall-clock time for reproducible
+/// builds, so instead of reading the clock, `uuid_v7!` takes the
+/// 48-bit Unix-millisecond timestamp as a literal, optionally
+/// followed by a seed for the random bits (filled deterministically
+/// from a fixed pad if omitted):
+///
+/// ```
+/// use typenum_uuid::uuid_v7;
+/// type Older = uuid_v7!(1_700_000_000_000);
+/// type Newer = uuid_v7!(1_700_000_000_001, 42);
+/// ```
+///
+/// Because the timestamp occupies the high bits of the UUID,
+/// `typenum`'s `IsLess`/`IsGreater` can compare two `uuid_v7!` types
+/// to recover their ordering at the type level.
+#[proc_macro]
+pub fn uuid_v7(args: TokenStream) -> TokenStream {
+    let input = match syn::parse::<V7MacroInput>(args) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let rand_bytes = v7_rand_bytes(input.seed);
+    let uuid = uuid::Builder::from_unix_timestamp_millis(input.millis, &rand_bytes).into_uuid();
+    uuid_to_tokenstream(uuid, input.prefix.into_tokens()).into()
+}
+
+/// The namespace `#[identify(v5 = "...")]` hashes names under.
+/// Arbitrary, but fixed so that the same name always produces the
+/// same ID.
+const IDENTIFY_NAMESPACE: Uuid = Uuid::from_u128(0x2f9d9b6e0b9a4d0a9d2a1f7e6b2d9b4c);
+
+/// How `#[identify]` should pick the UUID it stamps onto its item.
+enum IdentifyMode {
+    /// `#[identify]`: a fresh random UUID, same as `uuid_new_v4!()`.
+    V4,
+    /// `#[identify(v5 = "name")]`: a deterministic UUID, same as
+    /// `uuid_v5!(IDENTIFY_NAMESPACE, "name")`.
+    V5(LitStr),
+}
+
+/// The arguments to `#[identify(...)]`: an optional `v5 = "name"`
+/// to make the ID deterministic, and the usual optional
+/// `| path::to::typenum`.
+struct IdentifyArgs {
+    mode: IdentifyMode,
+    prefix: TypenumPrefix,
+}
+
+impl Parse for IdentifyArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mode = if input.peek(kw::v5) {
+            input.parse::<kw::v5>()?;
+            input.parse::<Token![=]>()?;
+            let name: LitStr = input.parse()?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            IdentifyMode::V5(name)
+        } else {
+            IdentifyMode::V4
+        };
+        Ok(IdentifyArgs { mode, prefix: input.parse()? })
+    }
+}
+
+/// Build the `impl typenum_uuid_core::Id for ...` block that
+/// `#[identify]` appends after the struct, enum, or impl block it
+/// annotates, carrying over that item's generics.
+fn identify_impl(item: &syn::Item, id_tokens: TokenStream2) -> syn::Result<TokenStream2> {
+    let (impl_generics, self_ty, where_clause) = match item {
+        syn::Item::Struct(item) => {
+            let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+            let ident = &item.ident;
+            (impl_generics, quote::quote!(#ident #ty_generics), where_clause)
+        }
+        syn::Item::Enum(item) => {
+            let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+            let ident = &item.ident;
+            (impl_generics, quote::quote!(#ident #ty_generics), where_clause)
+        }
+        syn::Item::Impl(item) => {
+            let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+            let self_ty = &item.self_ty;
+            (impl_generics, quote::quote!(#self_ty), where_clause)
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                item,
+                "#[identify] can only be applied to a struct, enum, or impl block",
+            ));
+        }
+    };
+
+    Ok(quote::quote! {
+        impl #impl_generics ::typenum_uuid_core::Id for #self_ty #where_clause {
+            type ID = #id_tokens;
+        }
+    })
+}
+
+/// Stamp a unique type-level `Id::ID` onto a struct, enum, or impl
+/// block
+///
+/// This replaces the boilerplate of writing `impl Id for T1 { type
+/// ID = uuid_new_v4!(); }` for every type that needs an identity:
+///
+/// ```
+/// use typenum_uuid::identify;
+/// use typenum_uuid_core::Id;
+///
+/// #[identify]
+/// struct T1;
+///
+/// #[identify(v5 = "t2")]
+/// struct T2;
+///
+/// fn assert_id<T: Id>() {}
+/// assert_id::<T1>();
+/// assert_id::<T2>();
+/// ```
+///
+/// By default the ID is a fresh random UUID, as if written with
+/// [`uuid_new_v4!`](macro@crate::uuid_new_v4). Passing `v5 = "name"`
+/// makes it deterministic instead, as if written with
+/// [`uuid_v5!`](macro@crate::uuid_v5) against a namespace fixed by
+/// this crate. Either form accepts the usual trailing
+/// `| path::to::typenum`.
+///
+/// The generated `impl` is of [`typenum_uuid_core::Id`], not a trait
+/// of this crate -- `typenum_uuid` is a proc-macro crate, so it can't
+/// export one itself.
+#[proc_macro_attribute]
+pub fn identify(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match syn::parse::<IdentifyArgs>(attr) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let parsed_item = match syn::parse::<syn::Item>(item.clone()) {
+        Ok(item) => item,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let uuid = match args.mode {
+        IdentifyMode::V4 => Uuid::new_v4(),
+        IdentifyMode::V5(name) => Uuid::new_v5(&IDENTIFY_NAMESPACE, name.value().as_bytes()),
+    };
+    let id_tokens = uuid_to_tokenstream(uuid, args.prefix.into_tokens());
+
+    let impl_block = match identify_impl(&parsed_item, id_tokens) {
+        Ok(tokens) => tokens,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut output: TokenStream2 = item.into();
+    output.extend(impl_block);
+    output.into()
+}
+
+/// Construct a typenum UUID as a byte array
+///
+/// Takes the same argument syntax as [`uuid!`](macro@crate::uuid),
+/// but instead of a single `Unsigned` type, returns a
+/// `typenum::TArr` of the UUID's 16 bytes, each a `U0..U255`
+/// constant, for code that wants byte-addressable access:
+///
+/// ```
+/// # use typenum_uuid::uuid_bytes;
+/// type Bytes = uuid_bytes!(a65ff38d-b5b2-48d0-b03a-bdf468523d2e);
+/// ```
+#[proc_macro]
+pub fn uuid_bytes(args: TokenStream) -> TokenStream {
+    let input = match syn::parse::<UuidMacroInput>(args) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let uuid = match Uuid::parse_str(&input.lit.text) {
+        Ok(uuid) => uuid,
+        Err(e) => {
+            return syn::Error::new(input.lit.span, format!("invalid UUID: {}", e))
+                .to_compile_error()
+                .into();
+        }
+    };
+    uuid_bytes_to_tokenstream(uuid, input.prefix.into_tokens()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypenumUint;
+
+    impl TypenumUint {
+        /// The innermost (most significant) bit, or `None` for
+        /// `UTerm`.
+        fn top_bit(&self) -> Option<bool> {
+            match self {
+                Self::Term => None,
+                Self::Lsb(high, bit) => high.top_bit().or(Some(*bit)),
+            }
+        }
+    }
+
+    #[test]
+    fn all_zero_uuid_is_uterm() {
+        let value = TypenumUint::from(0u128);
+        assert!(matches!(value, TypenumUint::Term));
+        assert!(value.is_canonical());
+    }
+
+    #[test]
+    fn nonzero_uuid_top_bit_is_one() {
+        let samples = [
+            1u128,
+            2,
+            255,
+            u128::MAX,
+            1u128 << 64,
+            0xa65ff38d_b5b2_48d0_b03a_bdf468523d2e_u128,
+        ];
+        for x in samples {
+            let value = TypenumUint::from(x);
+            assert!(value.is_canonical(), "{:#x} should canonicalize", x);
+            assert_eq!(value.top_bit(), Some(true), "top bit of {:#x} should be B1", x);
+        }
+    }
 }
@@ -63,3 +63,149 @@ fn literal() {
     assert_eq!(xcmp.as_u128(), X1::to_u128());
     assert_eq!(xcmp.as_u128(), X2::to_u128());
 }
+
+#[test]
+fn literal_exponent_lexed_group() {
+    use ::uuid::Uuid;
+    use typenum::Unsigned;
+
+    // The first group, `1e5f38d0`, lexes as a `LitFloat` rather than
+    // a `LitInt` because of the `e5` in the middle.
+    type X = ::typenum_uuid::uuid!(1e5f38d0-b5b2-48d0-b03a-bdf468523d2e);
+
+    let xcmp = Uuid::parse_str("1e5f38d0-b5b2-48d0-b03a-bdf468523d2e").unwrap();
+    assert_eq!(xcmp.as_u128(), X::to_u128());
+}
+
+#[test]
+fn v5_deterministic() {
+    use ::uuid::Uuid;
+    use typenum::Unsigned;
+
+    type X0 = ::typenum_uuid::uuid_v5!(NAMESPACE_DNS, "example.com");
+    type X1 = ::typenum_uuid::uuid_v5!(NAMESPACE_DNS, "example.com");
+    type X2 = ::typenum_uuid::uuid_v5!(NAMESPACE_URL, "example.com" | crate::fake_typenum);
+
+    let xcmp = Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"example.com");
+
+    assert_eq!(xcmp.as_u128(), X0::to_u128());
+    assert_eq!(X0::to_u128(), X1::to_u128());
+    assert_ne!(xcmp.as_u128(), Uuid::new_v5(&Uuid::NAMESPACE_URL, b"example.com").as_u128());
+
+    let y: X2 = Default::default();
+    #[allow(irrefutable_let_patterns)]
+    if let fake_typenum::UInt(_, _) = y {}
+    else { panic!("Proc macro didn't use alternate implementation"); }
+}
+
+#[test]
+fn v3_deterministic() {
+    use ::uuid::Uuid;
+    use typenum::Unsigned;
+
+    type X0 = ::typenum_uuid::uuid_v3!(NAMESPACE_DNS, "example.com");
+    type X1 = ::typenum_uuid::uuid_v3!(
+        a65ff38d-b5b2-48d0-b03a-bdf468523d2e, "example.com"
+    );
+
+    let xcmp = Uuid::new_v3(&Uuid::NAMESPACE_DNS, b"example.com");
+
+    assert_eq!(xcmp.as_u128(), X0::to_u128());
+    assert_ne!(X0::to_u128(), X1::to_u128());
+}
+
+#[test]
+fn v7_ordered() {
+    use typenum::{IsLess, Unsigned, True};
+
+    type Older = ::typenum_uuid::uuid_v7!(1_700_000_000_000);
+    type Newer = ::typenum_uuid::uuid_v7!(1_700_000_000_001);
+    type Repeat = ::typenum_uuid::uuid_v7!(1_700_000_000_000);
+
+    fn assert_less<A: IsLess<B, Output = True>, B>() {}
+    assert_less::<Older, Newer>();
+
+    assert_eq!(Older::to_u128(), Repeat::to_u128());
+}
+
+#[test]
+fn v7_seeded() {
+    use typenum::Unsigned;
+
+    type X0 = ::typenum_uuid::uuid_v7!(1_700_000_000_000, 42);
+    type X1 = ::typenum_uuid::uuid_v7!(1_700_000_000_000, 42);
+    type X2 = ::typenum_uuid::uuid_v7!(1_700_000_000_000, 43);
+
+    assert_eq!(X0::to_u128(), X1::to_u128());
+    assert_ne!(X0::to_u128(), X2::to_u128());
+}
+
+#[test]
+fn identify_struct() {
+    use typenum::{Unsigned, IsEqual, False};
+    use typenum_uuid::identify;
+    use typenum_uuid_core::Id;
+
+    #[identify]
+    struct T1;
+
+    #[identify]
+    struct T2;
+
+    #[identify(v5 = "identify_struct::t3")]
+    struct T3;
+
+    #[identify(v5 = "identify_struct::t3")]
+    struct T4;
+
+    fn assert_different<A: Id, B: Id>()
+    where
+        A::ID: IsEqual<B::ID, Output = False>,
+    {
+    }
+
+    assert_different::<T1, T2>();
+    assert_different::<T1, T3>();
+    assert_eq!(<T3 as Id>::ID::to_u128(), <T4 as Id>::ID::to_u128());
+}
+
+#[test]
+fn identify_impl_block() {
+    use typenum_uuid::identify;
+    use typenum_uuid_core::Id;
+
+    struct Wrapper<T>(T);
+
+    #[identify(v5 = "identify_impl_block::wrapper")]
+    impl<T> Wrapper<T> {}
+
+    use typenum::Unsigned;
+    let _ = <Wrapper<u8> as Id>::ID::to_u128();
+}
+
+#[test]
+fn bytes_round_trip() {
+    use ::uuid::Uuid;
+    use typenum::Unsigned;
+    use typenum_uuid::uuid_bytes;
+
+    type Bytes = uuid_bytes!(a65ff38d-b5b2-48d0-b03a-bdf468523d2e);
+
+    trait ArrToVec {
+        fn to_vec() -> Vec<u8>;
+    }
+    impl ArrToVec for typenum::ATerm {
+        fn to_vec() -> Vec<u8> { Vec::new() }
+    }
+    impl<H: Unsigned, T: ArrToVec> ArrToVec for typenum::TArr<H, T> {
+        fn to_vec() -> Vec<u8> {
+            let mut v = vec![H::to_u8()];
+            v.extend(T::to_vec());
+            v
+        }
+    }
+
+    let bytes = <Bytes as ArrToVec>::to_vec();
+    let xcmp = Uuid::parse_str("a65ff38d-b5b2-48d0-b03a-bdf468523d2e").unwrap();
+    assert_eq!(bytes, xcmp.as_bytes());
+}
@@ -0,0 +1,15 @@
+//! Support types shared with [`typenum_uuid`](https://docs.rs/typenum_uuid).
+//!
+//! `typenum_uuid` is a `proc-macro = true` crate, and those may only
+//! export `#[proc_macro]`/`#[proc_macro_derive]`/`#[proc_macro_attribute]`
+//! functions -- no plain traits, structs, or re-exports. Anything that
+//! needs to be part of its public surface but isn't itself a macro,
+//! like the [`Id`] trait that `#[identify]` implements, lives here
+//! instead.
+
+/// Implemented by types that have been assigned a type-level UUID,
+/// either by hand (`impl Id for T1 { type ID = typenum_uuid::uuid_new_v4!(); }`)
+/// or via `#[typenum_uuid::identify]`.
+pub trait Id {
+    type ID;
+}